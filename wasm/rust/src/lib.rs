@@ -40,6 +40,418 @@ pub fn calculate_point(real: f64, imag: f64, max_iterations: u32, escape_radius:
     max_iterations
 }
 
+/// Calculate a continuous (smooth) iteration count for gradient coloring
+///
+/// The plain integer escape count bands visibly when mapped to a color
+/// gradient. This applies the normalized iteration count formula
+/// `mu = n + 1 - ln(ln(|z|)) / ln(2)`, where `n` is the iteration at which
+/// `z` escaped and `|z|` is its modulus a couple of iterations past the
+/// escape test. The extra iterations keep the overshoot past the bailout
+/// small, which is what keeps the log-log term well-behaved, so a larger
+/// `escape_radius` (e.g. 256 rather than 2) should be used by callers.
+///
+/// # Arguments
+/// * `real` - Real component of the complex number c
+/// * `imag` - Imaginary component of the complex number c
+/// * `max_iterations` - Maximum number of iterations to perform
+/// * `escape_radius` - Threshold beyond which a point is considered escaped
+///
+/// # Returns
+/// A fractional iteration count suitable for smooth palettes, or
+/// `max_iterations` as a float if the point never escapes
+#[wasm_bindgen]
+pub fn calculate_point_smooth(
+    real: f64,
+    imag: f64,
+    max_iterations: u32,
+    escape_radius: f64,
+) -> f64 {
+    let c_real = real;
+    let c_imag = imag;
+
+    let mut z_real = 0.0;
+    let mut z_imag = 0.0;
+
+    let escape_radius_squared = escape_radius * escape_radius;
+
+    for iteration in 0..max_iterations {
+        let z_magnitude_squared = z_real * z_real + z_imag * z_imag;
+
+        if z_magnitude_squared > escape_radius_squared {
+            // Overshoot past the bailout a couple more iterations so the
+            // log-log term below is evaluated on a small, stable overshoot.
+            // Those extra steps count toward the iteration total too, since
+            // the formula expects n and |z| to come from the same step.
+            let mut n = iteration as f64;
+            for _ in 0..2 {
+                let z_real_temp = z_real * z_real - z_imag * z_imag + c_real;
+                z_imag = 2.0 * z_real * z_imag + c_imag;
+                z_real = z_real_temp;
+                n += 1.0;
+            }
+
+            let modulus = (z_real * z_real + z_imag * z_imag).sqrt();
+            return n + 1.0 - (modulus.ln().ln()) / std::f64::consts::LN_2;
+        }
+
+        let z_real_temp = z_real * z_real - z_imag * z_imag + c_real;
+        z_imag = 2.0 * z_real * z_imag + c_imag;
+        z_real = z_real_temp;
+    }
+
+    max_iterations as f64
+}
+
+/// Calculate the number of iterations for a point in a Julia set
+///
+/// Generalizes the same `z = z*z + c` escape loop used by `calculate_point`,
+/// but lets the caller supply the starting `z` and the additive constant `c`
+/// independently instead of deriving both from the pixel. Mandelbrot is the
+/// special case `z0 = 0, c = pixel`; Julia sets are `z0 = pixel, c = fixed`.
+///
+/// # Arguments
+/// * `z_real`, `z_imag` - Starting value of z
+/// * `c_real`, `c_imag` - Fixed additive constant c
+/// * `max_iterations` - Maximum number of iterations to perform
+/// * `escape_radius` - Threshold beyond which a point is considered escaped
+///
+/// # Returns
+/// The number of iterations before escape, or max_iterations if the point doesn't escape
+#[wasm_bindgen]
+pub fn calculate_point_julia(
+    z_real: f64,
+    z_imag: f64,
+    c_real: f64,
+    c_imag: f64,
+    max_iterations: u32,
+    escape_radius: f64,
+) -> u32 {
+    let mut z_real = z_real;
+    let mut z_imag = z_imag;
+
+    let escape_radius_squared = escape_radius * escape_radius;
+
+    for iteration in 0..max_iterations {
+        let z_magnitude_squared = z_real * z_real + z_imag * z_imag;
+
+        if z_magnitude_squared > escape_radius_squared {
+            return iteration;
+        }
+
+        let z_real_temp = z_real * z_real - z_imag * z_imag + c_real;
+        z_imag = 2.0 * z_real * z_imag + c_imag;
+        z_real = z_real_temp;
+    }
+
+    max_iterations
+}
+
+/// Calculate the exterior distance estimate for a point near the Mandelbrot set
+///
+/// Escape iteration counts only hint at the set's thin filaments; the
+/// distance estimator gives the true shape. Alongside `z = z*z + c`, this
+/// tracks the complex derivative `dz` via `dz = 2*z*dz + 1`, initialized to
+/// `dz = 0`. On escape, the exterior distance is `dist = |z| * ln(|z|) / |dz|`.
+/// A generous `escape_radius` keeps the logarithm well-conditioned.
+///
+/// # Arguments
+/// * `real` - Real component of the complex number c
+/// * `imag` - Imaginary component of the complex number c
+/// * `max_iterations` - Maximum number of iterations to perform
+/// * `escape_radius` - Threshold beyond which a point is considered escaped
+///
+/// # Returns
+/// The estimated distance to the set boundary, or `0.0` for points that
+/// never escape
+#[wasm_bindgen]
+pub fn calculate_distance(real: f64, imag: f64, max_iterations: u32, escape_radius: f64) -> f64 {
+    let c_real = real;
+    let c_imag = imag;
+
+    let mut z_real = 0.0;
+    let mut z_imag = 0.0;
+
+    let mut dz_real: f64 = 0.0;
+    let mut dz_imag: f64 = 0.0;
+
+    let escape_radius_squared = escape_radius * escape_radius;
+
+    for _ in 0..max_iterations {
+        let z_magnitude_squared = z_real * z_real + z_imag * z_imag;
+
+        if z_magnitude_squared > escape_radius_squared {
+            let z_magnitude = z_magnitude_squared.sqrt();
+            let dz_magnitude = (dz_real * dz_real + dz_imag * dz_imag).sqrt();
+            return z_magnitude * z_magnitude.ln() / dz_magnitude;
+        }
+
+        // dz = 2*z*dz + 1
+        let dz_real_temp = 2.0 * (z_real * dz_real - z_imag * dz_imag) + 1.0;
+        dz_imag = 2.0 * (z_real * dz_imag + z_imag * dz_real);
+        dz_real = dz_real_temp;
+
+        // z = z*z + c
+        let z_real_temp = z_real * z_real - z_imag * z_imag + c_real;
+        z_imag = 2.0 * z_real * z_imag + c_imag;
+        z_real = z_real_temp;
+    }
+
+    0.0
+}
+
+/// Calculate the number of iterations for a point, with early-bailout optimizations
+///
+/// Deep renders spend most of their budget on interior points that run to
+/// `max_iterations`. This variant adds three optimizations on top of the
+/// plain escape loop in `calculate_point`:
+///
+/// 1. A cardioid/period-2 bulb membership test run once before iterating,
+///    so points known to be interior return `max_iterations` immediately.
+/// 2. Periodicity detection: a reference `z` is snapshotted periodically and
+///    compared against the current `z`; if the orbit returns close to the
+///    snapshot, it is cyclic and therefore interior.
+/// 3. A reduced-multiply inner loop that reuses `zr*zr` and `zi*zi` across
+///    the escape test and the next iteration, saving one multiply per step.
+///
+/// # Arguments
+/// * `real` - Real component of the complex number c
+/// * `imag` - Imaginary component of the complex number c
+/// * `max_iterations` - Maximum number of iterations to perform
+/// * `escape_radius` - Threshold beyond which a point is considered escaped
+///
+/// # Returns
+/// The number of iterations before escape, or max_iterations if the point doesn't escape
+#[wasm_bindgen]
+pub fn calculate_point_fast(real: f64, imag: f64, max_iterations: u32, escape_radius: f64) -> u32 {
+    let cr = real;
+    let ci = imag;
+
+    // Main cardioid test: q * (q + (cr - 0.25)) <= 0.25 * ci^2
+    let q = (cr - 0.25) * (cr - 0.25) + ci * ci;
+    if q * (q + (cr - 0.25)) <= 0.25 * ci * ci {
+        return max_iterations;
+    }
+
+    // Period-2 bulb test: (cr + 1)^2 + ci^2 <= 1/16
+    if (cr + 1.0) * (cr + 1.0) + ci * ci <= 0.0625 {
+        return max_iterations;
+    }
+
+    let escape_radius_squared = escape_radius * escape_radius;
+
+    let mut zr = 0.0;
+    let mut zi = 0.0;
+    let mut zr2 = 0.0;
+    let mut zi2 = 0.0;
+
+    // Periodicity detection state (Brent-style): a snapshot of z is taken
+    // once more than `check_interval` iterations have passed since the
+    // previous one, and the orbit is compared against that snapshot on every
+    // iteration in between. The interval doubles after each snapshot, so
+    // each snapshot is compared against a longer stretch of the orbit. The
+    // comparison runs before the snapshot is refreshed, so a new snapshot is
+    // first compared on the following iteration — comparing on the very
+    // same pass would make every reset look periodic. Start
+    // the snapshot at NaN so no comparison can match before the first one
+    // is actually taken.
+    let mut check_real = f64::NAN;
+    let mut check_imag = f64::NAN;
+    let mut check_period = 0u32;
+    let mut check_interval = 20u32;
+    const PERIODICITY_EPSILON: f64 = 1e-12;
+
+    for iteration in 0..max_iterations {
+        if zr2 + zi2 > escape_radius_squared {
+            return iteration;
+        }
+
+        zi = 2.0 * zr * zi + ci;
+        zr = zr2 - zi2 + cr;
+
+        zr2 = zr * zr;
+        zi2 = zi * zi;
+
+        if (zr - check_real).abs() < PERIODICITY_EPSILON && (zi - check_imag).abs() < PERIODICITY_EPSILON {
+            return max_iterations;
+        }
+
+        check_period += 1;
+        if check_period > check_interval {
+            // Refresh the reference point and double the interval before the
+            // next snapshot, as in the classic Brent-style periodicity check.
+            check_real = zr;
+            check_imag = zi;
+            check_period = 0;
+            check_interval = check_interval.saturating_mul(2);
+        }
+    }
+
+    max_iterations
+}
+
+/// Calculate an entire image of iteration counts in a single call
+///
+/// Maps each pixel `(px, py)` in a `width` x `height` grid onto the complex
+/// plane rectangle `[x_min, x_max] x [y_min, y_max]` and runs the same
+/// escape-time loop as `calculate_point`. Doing this for the whole frame in
+/// one call avoids crossing the WASM/JS boundary once per pixel.
+///
+/// # Arguments
+/// * `width` - Image width in pixels
+/// * `height` - Image height in pixels
+/// * `x_min`, `x_max` - Real-axis bounds of the viewport
+/// * `y_min`, `y_max` - Imaginary-axis bounds of the viewport
+/// * `max_iterations` - Maximum number of iterations to perform per pixel
+/// * `escape_radius` - Threshold beyond which a point is considered escaped
+///
+/// # Returns
+/// A row-major `Vec<u32>` of length `width * height`, one iteration count
+/// per pixel, readable from JS as a `Uint32Array`.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_region(
+    width: u32,
+    height: u32,
+    x_min: f64,
+    x_max: f64,
+    y_min: f64,
+    y_max: f64,
+    max_iterations: u32,
+    escape_radius: f64,
+) -> Vec<u32> {
+    let mut buffer = vec![0u32; (width * height) as usize];
+
+    for py in 0..height {
+        let cy = y_min + (y_max - y_min) * (py as f64) / (height as f64);
+        for px in 0..width {
+            let cx = x_min + (x_max - x_min) * (px as f64) / (width as f64);
+            buffer[(py * width + px) as usize] =
+                calculate_point(cx, cy, max_iterations, escape_radius);
+        }
+    }
+
+    buffer
+}
+
+/// Palette used by `render_rgba` to map a smooth iteration count to a color
+const PALETTE_GRAYSCALE: u32 = 0;
+const PALETTE_HSL: u32 = 1;
+const PALETTE_BANDED: u32 = 2;
+
+/// Convert an HSL color (hue in `[0, 1)`, saturation and lightness in `[0, 1]`) to RGB bytes
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    let c = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h_prime = hue * 6.0;
+    let x = c * (1.0 - (h_prime % 2.0 - 1.0).abs());
+    let (r1, g1, b1) = match h_prime as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = lightness - c / 2.0;
+    (
+        (((r1 + m) * 255.0).round()) as u8,
+        (((g1 + m) * 255.0).round()) as u8,
+        (((b1 + m) * 255.0).round()) as u8,
+    )
+}
+
+/// Map a smooth iteration count to an RGB color under the given palette
+///
+/// Interior points (`smooth >= max_iterations as f64`) are always black.
+fn color_for_iteration(smooth: f64, max_iterations: u32, palette_id: u32) -> (u8, u8, u8) {
+    if smooth >= max_iterations as f64 {
+        return (0, 0, 0);
+    }
+
+    let fraction = (smooth / max_iterations as f64).clamp(0.0, 1.0);
+
+    // Any unrecognized palette_id falls back to grayscale.
+    let palette_id = if palette_id == PALETTE_HSL || palette_id == PALETTE_BANDED {
+        palette_id
+    } else {
+        PALETTE_GRAYSCALE
+    };
+
+    match palette_id {
+        PALETTE_HSL => hsl_to_rgb(fraction, 1.0, 0.5),
+        PALETTE_BANDED => {
+            // Discrete character-style banding: a handful of fixed shades.
+            const BANDS: [(u8, u8, u8); 8] = [
+                (25, 7, 26),
+                (9, 1, 47),
+                (4, 4, 73),
+                (0, 7, 100),
+                (12, 44, 138),
+                (24, 82, 177),
+                (57, 125, 209),
+                (134, 181, 229),
+            ];
+            let band = ((fraction * BANDS.len() as f64) as usize).min(BANDS.len() - 1);
+            BANDS[band]
+        }
+        _ => {
+            // PALETTE_GRAYSCALE
+            let level = (fraction * 255.0).round() as u8;
+            (level, level, level)
+        }
+    }
+}
+
+/// Render an entire image directly to an RGBA pixel buffer
+///
+/// Computes the smooth iteration value per pixel (see `calculate_point_smooth`)
+/// and maps it to an RGBA byte quadruple via the selected palette, producing a
+/// buffer ready for `putImageData`. Doing this in Rust avoids a per-pixel
+/// boundary crossing and a second coloring pass in JavaScript.
+///
+/// # Arguments
+/// * `width` - Image width in pixels
+/// * `height` - Image height in pixels
+/// * `x_min`, `x_max` - Real-axis bounds of the viewport
+/// * `y_min`, `y_max` - Imaginary-axis bounds of the viewport
+/// * `max_iterations` - Maximum number of iterations to perform per pixel
+/// * `escape_radius` - Threshold beyond which a point is considered escaped
+/// * `palette_id` - `0` for grayscale, `1` for an HSL hue sweep, `2` for discrete banding
+///
+/// # Returns
+/// A row-major `Vec<u8>` of length `width * height * 4`, four bytes (R, G, B, A) per pixel
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn render_rgba(
+    width: u32,
+    height: u32,
+    x_min: f64,
+    x_max: f64,
+    y_min: f64,
+    y_max: f64,
+    max_iterations: u32,
+    escape_radius: f64,
+    palette_id: u32,
+) -> Vec<u8> {
+    let mut buffer = vec![0u8; (width * height * 4) as usize];
+
+    for py in 0..height {
+        let cy = y_min + (y_max - y_min) * (py as f64) / (height as f64);
+        for px in 0..width {
+            let cx = x_min + (x_max - x_min) * (px as f64) / (width as f64);
+            let smooth = calculate_point_smooth(cx, cy, max_iterations, escape_radius);
+            let (r, g, b) = color_for_iteration(smooth, max_iterations, palette_id);
+
+            let offset = ((py * width + px) * 4) as usize;
+            buffer[offset] = r;
+            buffer[offset + 1] = g;
+            buffer[offset + 2] = b;
+            buffer[offset + 3] = 255;
+        }
+    }
+
+    buffer
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,4 +477,177 @@ mod tests {
         let iterations = calculate_point(-0.5, 0.5, max_iter, 2.0);
         assert!(iterations <= max_iter);
     }
+
+    #[test]
+    fn test_region_matches_point_by_point() {
+        let (width, height) = (8, 6);
+        let (x_min, x_max, y_min, y_max) = (-2.0, 1.0, -1.0, 1.0);
+        let max_iter = 50;
+        let escape_radius = 2.0;
+
+        let region = calculate_region(
+            width,
+            height,
+            x_min,
+            x_max,
+            y_min,
+            y_max,
+            max_iter,
+            escape_radius,
+        );
+
+        for py in 0..height {
+            let cy = y_min + (y_max - y_min) * (py as f64) / (height as f64);
+            for px in 0..width {
+                let cx = x_min + (x_max - x_min) * (px as f64) / (width as f64);
+                let expected = calculate_point(cx, cy, max_iter, escape_radius);
+                assert_eq!(region[(py * width + px) as usize], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_region_buffer_length() {
+        let region = calculate_region(4, 3, -2.0, 1.0, -1.0, 1.0, 20, 2.0);
+        assert_eq!(region.len(), 4 * 3);
+    }
+
+    #[test]
+    fn test_smooth_point_in_set() {
+        // Point (0, 0) never escapes, so the smooth count is just max_iterations
+        let smooth = calculate_point_smooth(0.0, 0.0, 100, 256.0);
+        assert_eq!(smooth, 100.0);
+    }
+
+    #[test]
+    fn test_smooth_point_escapes_quickly() {
+        let smooth = calculate_point_smooth(2.0, 2.0, 100, 256.0);
+        assert!(smooth > 0.0 && smooth < 10.0);
+    }
+
+    #[test]
+    fn test_smooth_tracks_integer_count() {
+        // The fractional part should refine, not contradict, the integer count
+        let real = -0.75;
+        let imag = 0.1;
+        let integer = calculate_point(real, imag, 100, 256.0);
+        let smooth = calculate_point_smooth(real, imag, 100, 256.0);
+        assert!((smooth - integer as f64).abs() < 3.0);
+    }
+
+    #[test]
+    fn test_julia_matches_mandelbrot_special_case() {
+        // z0 = 0, c = pixel should reproduce calculate_point exactly
+        let real = -0.5;
+        let imag = 0.5;
+        let mandelbrot = calculate_point(real, imag, 100, 2.0);
+        let julia = calculate_point_julia(0.0, 0.0, real, imag, 100, 2.0);
+        assert_eq!(mandelbrot, julia);
+    }
+
+    #[test]
+    fn test_julia_point_escapes_quickly() {
+        // z0 = pixel already outside the escape radius escapes immediately
+        let iterations = calculate_point_julia(2.0, 2.0, 0.0, 0.0, 100, 2.0);
+        assert!(iterations < 10);
+    }
+
+    #[test]
+    fn test_julia_iteration_bounded() {
+        let max_iter = 256;
+        let iterations = calculate_point_julia(-0.5, 0.5, -0.4, 0.6, max_iter, 2.0);
+        assert!(iterations <= max_iter);
+    }
+
+    #[test]
+    fn test_distance_interior_point_is_zero() {
+        let distance = calculate_distance(0.0, 0.0, 100, 256.0);
+        assert_eq!(distance, 0.0);
+    }
+
+    #[test]
+    fn test_distance_far_point_is_positive() {
+        let distance = calculate_distance(2.0, 2.0, 100, 256.0);
+        assert!(distance > 0.0);
+    }
+
+    #[test]
+    fn test_distance_shrinks_near_boundary() {
+        // Points closer to the boundary should have a smaller distance estimate
+        // than points well outside the set.
+        let far = calculate_distance(2.0, 2.0, 256, 256.0);
+        let near = calculate_distance(-0.75, 0.1, 256, 256.0);
+        assert!(near < far);
+    }
+
+    #[test]
+    fn test_fast_point_in_set() {
+        let iterations = calculate_point_fast(0.0, 0.0, 1000, 2.0);
+        assert_eq!(iterations, 1000);
+    }
+
+    #[test]
+    fn test_fast_point_escapes_quickly() {
+        let iterations = calculate_point_fast(2.0, 2.0, 100, 2.0);
+        assert!(iterations < 10);
+    }
+
+    #[test]
+    fn test_fast_matches_plain_escape_count() {
+        // Outside the cardioid/bulb shortcuts, the fast path should return
+        // exactly the same escape iteration as the plain loop. This includes
+        // a near-boundary filament point whose true escape iteration (71) is
+        // past the periodicity check's first snapshot, to exercise that the
+        // periodicity detection doesn't misclassify it as interior.
+        let cases = [
+            (2.0, 2.0),
+            (-1.5, 0.1),
+            (0.3, 0.5),
+            (-0.1, 0.8),
+            (-1.76, 0.0149999999999999),
+        ];
+        for (real, imag) in cases {
+            let plain = calculate_point(real, imag, 256, 2.0);
+            let fast = calculate_point_fast(real, imag, 256, 2.0);
+            assert_eq!(plain, fast, "mismatch at ({real}, {imag})");
+        }
+    }
+
+    #[test]
+    fn test_fast_cardioid_shortcut() {
+        // A point well inside the main cardioid should short-circuit to max_iterations
+        let iterations = calculate_point_fast(-0.5, 0.0, 10_000, 2.0);
+        assert_eq!(iterations, 10_000);
+    }
+
+    #[test]
+    fn test_fast_period_2_bulb_shortcut() {
+        // A point inside the period-2 bulb centered at (-1, 0)
+        let iterations = calculate_point_fast(-1.0, 0.0, 10_000, 2.0);
+        assert_eq!(iterations, 10_000);
+    }
+
+    #[test]
+    fn test_render_rgba_buffer_length() {
+        let buffer = render_rgba(4, 3, -2.0, 1.0, -1.0, 1.0, 20, 256.0, PALETTE_GRAYSCALE);
+        assert_eq!(buffer.len(), 4 * 3 * 4);
+    }
+
+    #[test]
+    fn test_render_rgba_interior_is_black() {
+        // (0, 0) is interior for every supported palette, and should render as
+        // opaque black regardless of which palette is selected.
+        for palette_id in [PALETTE_GRAYSCALE, PALETTE_HSL, PALETTE_BANDED] {
+            let buffer = render_rgba(1, 1, 0.0, 0.0, 0.0, 0.0, 100, 256.0, palette_id);
+            assert_eq!(&buffer[..], &[0, 0, 0, 255]);
+        }
+    }
+
+    #[test]
+    fn test_render_rgba_alpha_is_opaque() {
+        let buffer = render_rgba(4, 4, -2.0, 1.0, -1.0, 1.0, 50, 256.0, PALETTE_HSL);
+        for chunk in buffer.chunks(4) {
+            assert_eq!(chunk[3], 255);
+        }
+    }
 }